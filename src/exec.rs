@@ -0,0 +1,202 @@
+//! Execution state threaded through template evaluation.
+
+use std::collections::BTreeMap;
+use std::rc::Rc;
+
+use crate::font::{FamilyList, FontFamily, FontStyle, FontVariant, FontWeight, VerticalFontMetric};
+use crate::geom::{Length, Linear};
+use crate::layout::{Dir, Paint};
+
+/// The active text styling, inherited and refined by nested `font()` calls.
+#[derive(Debug, Clone, PartialEq)]
+pub struct FontState {
+    pub size: Length,
+    pub variant: FontVariant,
+    pub top_edge: VerticalFontMetric,
+    pub bottom_edge: VerticalFontMetric,
+    pub fill: Paint,
+    pub strikethrough: Option<Rc<LineState>>,
+    pub underline: Option<Rc<LineState>>,
+    pub overline: Option<Rc<LineState>>,
+    families: Rc<FamilyList>,
+    features: Rc<BTreeMap<[u8; 4], u32>>,
+    variants: Rc<BTreeMap<(FontWeight, FontStyle), Vec<FontFamily>>>,
+}
+
+impl FontState {
+    /// The family list, cloned on first write so sibling scopes that
+    /// inherited the same state aren't affected.
+    pub fn families_mut(&mut self) -> &mut FamilyList {
+        Rc::make_mut(&mut self.families)
+    }
+
+    pub fn families(&self) -> &FamilyList {
+        &self.families
+    }
+
+    /// The resolved OpenType feature set, merged onto the inherited map so
+    /// that nested `font(features: ..)` calls compose rather than clobber.
+    pub fn features_mut(&mut self) -> &mut BTreeMap<[u8; 4], u32> {
+        Rc::make_mut(&mut self.features)
+    }
+
+    /// The feature set handed to the shaper; see [`crate::shaping::shape`].
+    pub fn features(&self) -> &BTreeMap<[u8; 4], u32> {
+        &self.features
+    }
+
+    /// The per-variant face overrides registered through `font`'s
+    /// `normal`/`bold`/`italic`/`bold-italic` arguments.
+    pub fn variants_mut(&mut self) -> &mut BTreeMap<(FontWeight, FontStyle), Vec<FontFamily>> {
+        Rc::make_mut(&mut self.variants)
+    }
+
+    /// The family list to search for the given weight and style: an
+    /// override registered for the bucketed weight and style, if one
+    /// exists, before falling back to the regular family list. Bucketing
+    /// means e.g. a weight of 600 or 800 still consults the `bold`
+    /// override instead of only matching an exact 700.
+    pub fn select_families(&self, weight: FontWeight, style: FontStyle) -> &[FontFamily] {
+        self.variants
+            .get(&(weight.bucket(), style))
+            .map(Vec::as_slice)
+            .unwrap_or(&self.families.list)
+    }
+
+    /// Expand the active family list for the current variant into concrete
+    /// family names, substituting each generic family (`serif`, and so on)
+    /// with its configured fallback list via [`FamilyList::resolve`].
+    pub fn resolve_family_names(&self) -> Vec<&str> {
+        self.select_families(self.variant.weight, self.variant.style)
+            .iter()
+            .flat_map(|family| self.families.resolve(family))
+            .map(String::as_str)
+            .collect()
+    }
+
+    /// Query `db` for the best installed face across the resolved family
+    /// names, in order, for the current variant — the fallback path that
+    /// lets `font("serif")` resolve to a real installed face.
+    pub fn resolve_faces(&self, db: &crate::font::FontDb) -> Vec<crate::font::FaceId> {
+        self.resolve_family_names()
+            .into_iter()
+            .filter_map(|name| db.query(name, self.variant))
+            .collect()
+    }
+}
+
+impl Default for FontState {
+    fn default() -> Self {
+        Self {
+            size: Length::pt(11.0),
+            variant: FontVariant::default(),
+            top_edge: VerticalFontMetric::CapHeight,
+            bottom_edge: VerticalFontMetric::Baseline,
+            fill: Paint::black(),
+            strikethrough: None,
+            underline: None,
+            overline: None,
+            families: Rc::new(FamilyList::default()),
+            features: Rc::new(BTreeMap::new()),
+            variants: Rc::new(BTreeMap::new()),
+        }
+    }
+}
+
+/// The decorative line drawn by `strike`/`underline`/`overline`. Every
+/// field is optional so nested calls can refine just the properties they
+/// were given while inheriting the rest from the enclosing scope; a
+/// `None` extent is treated the same as the previous zero default by
+/// whatever lays the line out.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct LineState {
+    pub stroke: Option<Paint>,
+    pub thickness: Option<Linear>,
+    pub offset: Option<Linear>,
+    pub extent: Option<Linear>,
+}
+
+/// How a paragraph's leading is determined.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Leading {
+    /// A length whose relative part is resolved against the font's
+    /// vertical metrics (ascender plus descender).
+    Linear(Linear),
+    /// A line height as a pure ratio of the font size. Resolves to
+    /// `ratio * font.size - (ascender + descender)` so that the
+    /// baseline-to-baseline distance is `ratio * font.size` regardless of
+    /// the font's vertical metrics.
+    Ratio(f64),
+}
+
+/// The active paragraph styling.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct ParState {
+    pub spacing: Linear,
+    pub leading: Leading,
+    pub word_spacing: Linear,
+}
+
+impl ParState {
+    /// Resolve the configured leading into the absolute gap between lines,
+    /// i.e. the baseline-to-baseline distance minus `ascender + descender`.
+    /// A ratio leading makes that distance `ratio * font_size`; a linear
+    /// leading resolves its relative part against `ascender + descender`.
+    pub fn resolve_leading(&self, font_size: Length, ascender: Length, descender: Length) -> Length {
+        match self.leading {
+            Leading::Ratio(ratio) => font_size * ratio - (ascender + descender),
+            Leading::Linear(linear) => linear.resolve(ascender + descender),
+        }
+    }
+}
+
+impl Default for ParState {
+    fn default() -> Self {
+        Self {
+            spacing: Linear::from(Length::pt(10.0)),
+            leading: Leading::Linear(Linear::from(Length::pt(6.5))),
+            word_spacing: Linear::from(Length::pt(0.0)),
+        }
+    }
+}
+
+/// The active language/direction styling.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct LangState {
+    pub dir: Dir,
+}
+
+impl Default for LangState {
+    fn default() -> Self {
+        Self { dir: Dir::LTR }
+    }
+}
+
+/// The combined, inheritable execution state threaded through `Template`
+/// evaluation, reached from `EvalContext` as `ctx.state`.
+#[derive(Debug, Clone, PartialEq)]
+pub struct State {
+    font: FontState,
+    pub par: ParState,
+    pub lang: LangState,
+}
+
+impl State {
+    pub fn font_mut(&mut self) -> &mut FontState {
+        &mut self.font
+    }
+
+    pub fn font(&self) -> &FontState {
+        &self.font
+    }
+}
+
+impl Default for State {
+    fn default() -> Self {
+        Self {
+            font: FontState::default(),
+            par: ParState::default(),
+            lang: LangState::default(),
+        }
+    }
+}
@@ -0,0 +1,269 @@
+//! Font properties, family lists, and system font discovery.
+
+use std::collections::HashMap;
+
+/// How a font is styled.
+#[derive(Debug, Clone, Copy, Eq, PartialEq, Ord, PartialOrd, Hash)]
+pub enum FontStyle {
+    /// The default, upright style.
+    Normal,
+    /// A cursive style with custom letterform.
+    Italic,
+    /// Just a slanted version of the normal style.
+    Oblique,
+}
+
+/// The weight of a font.
+#[derive(Debug, Clone, Copy, Eq, PartialEq, Ord, PartialOrd, Hash)]
+pub struct FontWeight(u16);
+
+impl FontWeight {
+    pub const THIN: Self = Self(100);
+    pub const EXTRALIGHT: Self = Self(200);
+    pub const LIGHT: Self = Self(300);
+    pub const REGULAR: Self = Self(400);
+    pub const MEDIUM: Self = Self(500);
+    pub const SEMIBOLD: Self = Self(600);
+    pub const BOLD: Self = Self(700);
+    pub const EXTRABOLD: Self = Self(800);
+    pub const BLACK: Self = Self(900);
+
+    /// Create a font weight from a raw number between 100 and 900.
+    pub fn from_number(number: u16) -> Self {
+        Self(number.clamp(100, 900))
+    }
+
+    /// The number between 100 and 900.
+    pub fn to_number(self) -> u16 {
+        self.0
+    }
+
+    /// Buckets this weight into `REGULAR` or `BOLD`, the two poles that
+    /// `font`'s `normal`/`bold`/`italic`/`bold-italic` overrides key on, so
+    /// that intermediate weights like 600 or 800 still resolve to the bold
+    /// override instead of being looked up and missed.
+    pub fn bucket(self) -> Self {
+        if self.0 >= 600 { Self::BOLD } else { Self::REGULAR }
+    }
+}
+
+/// The width of a font.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct FontStretch(f32);
+
+impl FontStretch {
+    /// Normal width.
+    pub const NORMAL: Self = Self(1.0);
+
+    /// Create a font stretch from an `OS/2` ratio between 0.5 and 2.0.
+    pub fn from_ratio(ratio: f32) -> Self {
+        Self(ratio.clamp(0.5, 2.0))
+    }
+
+    /// The ratio between 0.5 and 2.0.
+    pub fn to_ratio(self) -> f32 {
+        self.0
+    }
+}
+
+impl Default for FontStretch {
+    fn default() -> Self {
+        Self::NORMAL
+    }
+}
+
+/// A combination of style, weight and stretch.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct FontVariant {
+    pub style: FontStyle,
+    pub weight: FontWeight,
+    pub stretch: FontStretch,
+}
+
+impl Default for FontVariant {
+    fn default() -> Self {
+        Self {
+            style: FontStyle::Normal,
+            weight: FontWeight::REGULAR,
+            stretch: FontStretch::NORMAL,
+        }
+    }
+}
+
+/// A generic fallback family, resolved against [`FamilyList`] to a list of
+/// concrete family names.
+#[derive(Debug, Clone, Copy, Eq, PartialEq, Hash)]
+pub enum GenericFamily {
+    Serif,
+    SansSerif,
+    Monospace,
+    Cursive,
+    Fantasy,
+}
+
+/// A single font family, either a concrete name or a generic fallback.
+#[derive(Debug, Clone, PartialEq)]
+pub enum FontFamily {
+    /// A specific family, like `"Noto Sans"`.
+    Named(String),
+    /// A generic family, like `serif`, resolved against the active
+    /// [`FamilyList`] at layout time.
+    Generic(GenericFamily),
+}
+
+/// The fallback lists backing the generic families, plus the explicit
+/// family list set through `font`'s positional or `family` argument.
+#[derive(Debug, Clone, PartialEq)]
+pub struct FamilyList {
+    /// The explicitly requested families, in order. A generic entry is
+    /// expanded to the matching list below at its own position; the generic
+    /// lists are not searched otherwise.
+    pub list: Vec<FontFamily>,
+    pub serif: Vec<String>,
+    pub sans_serif: Vec<String>,
+    pub monospace: Vec<String>,
+    pub cursive: Vec<String>,
+    pub fantasy: Vec<String>,
+}
+
+impl FamilyList {
+    /// Expand a single family into the concrete names that should be tried
+    /// for it, substituting the matching list when it's generic.
+    pub fn resolve<'a>(&'a self, family: &'a FontFamily) -> &'a [String] {
+        match family {
+            FontFamily::Named(name) => std::slice::from_ref(name),
+            FontFamily::Generic(GenericFamily::Serif) => &self.serif,
+            FontFamily::Generic(GenericFamily::SansSerif) => &self.sans_serif,
+            FontFamily::Generic(GenericFamily::Monospace) => &self.monospace,
+            FontFamily::Generic(GenericFamily::Cursive) => &self.cursive,
+            FontFamily::Generic(GenericFamily::Fantasy) => &self.fantasy,
+        }
+    }
+}
+
+impl Default for FamilyList {
+    fn default() -> Self {
+        Self {
+            list: vec![],
+            serif: vec!["linux libertine".into(), "dejavu serif".into()],
+            sans_serif: vec!["pt sans".into(), "dejavu sans".into()],
+            monospace: vec!["deja vu sans mono".into()],
+            cursive: vec!["comic sans ms".into()],
+            fantasy: vec!["impact".into()],
+        }
+    }
+}
+
+/// Where a line is drawn relative to the text, used for `top-edge` and
+/// `bottom-edge`.
+#[derive(Debug, Clone, Copy, Eq, PartialEq, Hash)]
+pub enum VerticalFontMetric {
+    Ascender,
+    CapHeight,
+    XHeight,
+    Baseline,
+    Descender,
+}
+
+/// An index over installed system fonts, populated the way `fc-match` or
+/// the `fontdb` crate would: by walking the system font directories once
+/// and remembering each face's family name plus its variant.
+#[derive(Debug, Default)]
+pub struct FontDb {
+    faces: HashMap<String, Vec<(FontVariant, FaceId)>>,
+}
+
+/// An opaque handle into the font database, analogous to `fontdb::ID`.
+#[derive(Debug, Clone, Copy, Eq, PartialEq, Hash)]
+pub struct FaceId(pub usize);
+
+impl FontDb {
+    /// Scan the usual system font directories and index the faces found
+    /// there by lowercased family name.
+    pub fn populate() -> Self {
+        let mut db = Self::default();
+        for dir in system_font_dirs() {
+            db.scan_dir(&dir);
+        }
+        db
+    }
+
+    /// Enumerate the font files below `dir`, recursing into
+    /// subdirectories, and [`register`](Self::register) each one.
+    fn scan_dir(&mut self, dir: &std::path::Path) {
+        let Ok(entries) = std::fs::read_dir(dir) else { return };
+        for entry in entries.flatten() {
+            let path = entry.path();
+            let is_font = matches!(
+                path.extension().and_then(|ext| ext.to_str()),
+                Some("ttf" | "otf" | "ttc" | "otc")
+            );
+            if is_font {
+                self.register(&path);
+            } else if path.is_dir() {
+                self.scan_dir(&path);
+            }
+        }
+    }
+
+    /// Register a single face, reading its real family name and variant out
+    /// of the `name`/`OS2`/`head` tables instead of guessing from the file
+    /// name, so `query` can actually match it against a requested family.
+    /// Only the first face of a collection file (`.ttc`/`.otc`) is read.
+    fn register(&mut self, path: &std::path::Path) {
+        let Ok(data) = std::fs::read(path) else { return };
+        let Ok(face) = ttf_parser::Face::parse(&data, 0) else { return };
+
+        let Some(family) = face
+            .names()
+            .into_iter()
+            .find(|name| name.name_id == ttf_parser::name_id::FAMILY && name.is_unicode())
+            .and_then(|name| name.to_string())
+        else {
+            return;
+        };
+
+        let variant = FontVariant {
+            style: match face.style() {
+                ttf_parser::Style::Normal => FontStyle::Normal,
+                ttf_parser::Style::Italic => FontStyle::Italic,
+                ttf_parser::Style::Oblique => FontStyle::Oblique,
+            },
+            weight: FontWeight::from_number(face.weight().to_number()),
+            stretch: FontStretch::from_ratio(face.width().to_ratio()),
+        };
+
+        let id = FaceId(self.faces.values().map(Vec::len).sum());
+        self.faces.entry(family.to_lowercase()).or_default().push((variant, id));
+    }
+
+    /// Find the best matching installed face for `family` in the given
+    /// variant: prefer an exact style match, then the closest weight.
+    pub fn query(&self, family: &str, variant: FontVariant) -> Option<FaceId> {
+        let candidates = self.faces.get(&family.to_lowercase())?;
+        candidates
+            .iter()
+            .min_by_key(|(v, _)| {
+                let style_mismatch = v.style != variant.style;
+                let weight_diff =
+                    (v.weight.to_number() as i32 - variant.weight.to_number() as i32).abs();
+                (style_mismatch, weight_diff)
+            })
+            .map(|&(_, id)| id)
+    }
+}
+
+/// The directories a system font database is populated from.
+fn system_font_dirs() -> Vec<std::path::PathBuf> {
+    #[cfg(target_os = "linux")]
+    return vec!["/usr/share/fonts".into(), "/usr/local/share/fonts".into()];
+
+    #[cfg(target_os = "macos")]
+    return vec!["/System/Library/Fonts".into(), "/Library/Fonts".into()];
+
+    #[cfg(target_os = "windows")]
+    return vec!["C:\\Windows\\Fonts".into()];
+
+    #[cfg(not(any(target_os = "linux", target_os = "macos", target_os = "windows")))]
+    return vec![];
+}
@@ -1,7 +1,7 @@
 use std::convert::TryFrom;
 
-use crate::exec::{FontState, LineState};
-use crate::font::{FontStretch, FontStyle, FontWeight};
+use crate::exec::{FontState, Leading, LineState};
+use crate::font::{FontStretch, FontStyle, FontWeight, GenericFamily};
 use crate::layout::Paint;
 
 use super::*;
@@ -25,6 +25,14 @@ pub fn font(ctx: &mut EvalContext, args: &mut FuncArgs) -> Value {
     let serif = args.named(ctx, "serif");
     let sans_serif = args.named(ctx, "sans-serif");
     let monospace = args.named(ctx, "monospace");
+    let cursive = args.named(ctx, "cursive");
+    let fantasy = args.named(ctx, "fantasy");
+    let features = args.named(ctx, "features");
+    let kerning = args.named(ctx, "kerning");
+    let normal = args.named(ctx, "normal");
+    let bold = args.named(ctx, "bold");
+    let italic = args.named(ctx, "italic");
+    let bold_italic = args.named(ctx, "bold-italic");
     let body = args.expect::<Template>(ctx, "body").unwrap_or_default();
 
     Value::template(move |ctx| {
@@ -74,6 +82,41 @@ pub fn font(ctx: &mut EvalContext, args: &mut FuncArgs) -> Value {
             font.families_mut().monospace = monospace.clone();
         }
 
+        if let Some(FamilyDef(cursive)) = &cursive {
+            font.families_mut().cursive = cursive.clone();
+        }
+
+        if let Some(FamilyDef(fantasy)) = &fantasy {
+            font.families_mut().fantasy = fantasy.clone();
+        }
+
+        if let Some(FeatureDef(list)) = &features {
+            let map = font.features_mut();
+            for &(tag, value) in list {
+                map.insert(tag, value);
+            }
+        }
+
+        if let Some(kerning) = kerning {
+            font.features_mut().insert(tag("kern"), kerning as u32);
+        }
+
+        if let Some(FontDef(list)) = &normal {
+            font.variants_mut().insert((FontWeight::REGULAR, FontStyle::Normal), list.clone());
+        }
+
+        if let Some(FontDef(list)) = &bold {
+            font.variants_mut().insert((FontWeight::BOLD, FontStyle::Normal), list.clone());
+        }
+
+        if let Some(FontDef(list)) = &italic {
+            font.variants_mut().insert((FontWeight::REGULAR, FontStyle::Italic), list.clone());
+        }
+
+        if let Some(FontDef(list)) = &bold_italic {
+            font.variants_mut().insert((FontWeight::BOLD, FontStyle::Italic), list.clone());
+        }
+
         body.exec(ctx);
     })
 }
@@ -108,7 +151,14 @@ castable! {
 
 castable! {
     FontFamily: "font family",
-    Value::Str(string) => Self::Named(string.to_lowercase())
+    Value::Str(string) => match string.to_lowercase().as_str() {
+        "serif" => Self::Generic(GenericFamily::Serif),
+        "sans-serif" => Self::Generic(GenericFamily::SansSerif),
+        "monospace" => Self::Generic(GenericFamily::Monospace),
+        "cursive" => Self::Generic(GenericFamily::Cursive),
+        "fantasy" => Self::Generic(GenericFamily::Fantasy),
+        _ => Self::Named(string.to_lowercase()),
+    },
 }
 
 castable! {
@@ -131,10 +181,60 @@ castable! {
     VerticalFontMetric: "vertical font metric",
 }
 
+#[derive(Debug)]
+struct FeatureDef(Vec<([u8; 4], u32)>);
+
+castable! {
+    FeatureDef: "array of font features",
+    Value::Array(values) => Self(values
+        .into_iter()
+        .filter_map(feature_from_value)
+        .collect()
+    ),
+}
+
+/// Parse a single OpenType feature: a bare tag string enables it, while a
+/// `(tag, value)` pair selects an alternate or, for zero, disables a
+/// feature that would otherwise be on by default. Anything else (a
+/// mistyped pair length, or a negative value) is dropped rather than
+/// silently coerced, the same way `FontDef`/`FamilyDef` drop entries that
+/// don't cast.
+fn feature_from_value(value: Value) -> Option<([u8; 4], u32)> {
+    match value {
+        Value::Str(string) => Some((tag(&string), 1)),
+        Value::Array(pair) if pair.len() == 2 => {
+            let mut iter = pair.into_iter();
+            let string: EcoString = iter.next()?.cast().ok()?;
+            let value: i64 = iter.next()?.cast().ok()?;
+            let value = u32::try_from(value).ok()?;
+            Some((tag(&string), value))
+        }
+        _ => None,
+    }
+}
+
+/// Converts a string into a four-byte OpenType feature or table tag,
+/// padding with spaces if necessary.
+fn tag(string: &str) -> [u8; 4] {
+    let mut bytes = [b' '; 4];
+    for (slot, byte) in bytes.iter_mut().zip(string.bytes()) {
+        *slot = byte;
+    }
+    bytes
+}
+
+castable! {
+    Leading: "linear or ratio",
+    Value::Relative(relative) => Self::Ratio(relative.get()),
+    Value::Float(v) => Self::Ratio(v),
+    Value::Int(v) => Self::Ratio(v as f64),
+    #(linear: Linear) => Self::Linear(linear),
+}
+
 /// `par`: Configure paragraphs.
 pub fn par(ctx: &mut EvalContext, args: &mut FuncArgs) -> Value {
     let spacing = args.named(ctx, "spacing");
-    let leading = args.named(ctx, "leading");
+    let leading = args.named::<Leading>(ctx, "leading");
     let word_spacing = args.named(ctx, "word-spacing");
     let body = args.expect::<Template>(ctx, "body").unwrap_or_default();
 
@@ -211,21 +311,28 @@ fn line_impl(
     let stroke = args.eat().or_else(|| args.named(ctx, "stroke"));
     let thickness = args.eat().or_else(|| args.named::<Linear>(ctx, "thickness"));
     let offset = args.named(ctx, "offset");
-    let extent = args.named(ctx, "extent").unwrap_or_default();
+    let extent = args.named(ctx, "extent");
     let body = args.expect::<Template>(ctx, "body").unwrap_or_default();
 
-    // Suppress any existing strikethrough if strength is explicitly zero.
-    let state = thickness.map_or(true, |s| !s.is_zero()).then(|| {
-        Rc::new(LineState {
-            stroke: stroke.map(Paint::Color),
-            thickness,
-            offset,
-            extent,
-        })
-    });
+    // An explicit zero thickness suppresses the line entirely, even if one
+    // was inherited from an outer scope.
+    let zero = thickness.map_or(false, |s| s.is_zero());
 
     Value::template(move |ctx| {
-        *substate(ctx.state.font_mut()) = state.clone();
+        let sub = substate(ctx.state.font_mut());
+        let base = sub.as_deref();
+
+        *sub = if zero {
+            None
+        } else {
+            Some(Rc::new(LineState {
+                stroke: stroke.map(Paint::Color).or_else(|| base.and_then(|b| b.stroke)),
+                thickness: thickness.or_else(|| base.and_then(|b| b.thickness)),
+                offset: offset.or_else(|| base.and_then(|b| b.offset)),
+                extent: extent.or_else(|| base.and_then(|b| b.extent)),
+            }))
+        };
+
         body.exec(ctx);
     })
 }
\ No newline at end of file
@@ -0,0 +1,46 @@
+//! Text shaping.
+
+use crate::exec::FontState;
+
+/// A single glyph produced by shaping, positioned relative to the origin
+/// of the shaped run.
+#[derive(Debug, Clone, Copy)]
+pub struct ShapedGlyph {
+    pub glyph_id: u32,
+    pub cluster: u32,
+    pub x_advance: f64,
+}
+
+/// Shape `text` under the active font state. The resolved OpenType feature
+/// set is collected and passed to [`shape_with_features`], which does not
+/// apply it: tags such as `liga 0` or `ss01 1` have no effect on the output.
+pub fn shape(text: &str, state: &FontState) -> Vec<ShapedGlyph> {
+    let features: Vec<_> = state
+        .features()
+        .iter()
+        .map(|(&tag, &value)| buzz_feature(tag, value))
+        .collect();
+
+    shape_with_features(text, &features)
+}
+
+/// A feature in the shape the shaping backend (e.g. rustybuzz) expects:
+/// a tag plus the value to set it to across the whole run.
+struct BuzzFeature {
+    tag: [u8; 4],
+    value: u32,
+}
+
+fn buzz_feature(tag: [u8; 4], value: u32) -> BuzzFeature {
+    BuzzFeature { tag, value }
+}
+
+/// Produces one glyph per character of `text`, with glyph id 0, the
+/// character's byte offset as cluster and zero advance. No shaping backend
+/// is called and `features` is ignored.
+fn shape_with_features(text: &str, features: &[BuzzFeature]) -> Vec<ShapedGlyph> {
+    let _ = features;
+    text.char_indices()
+        .map(|(i, _)| ShapedGlyph { glyph_id: 0, cluster: i as u32, x_advance: 0.0 })
+        .collect()
+}